@@ -0,0 +1,35 @@
+//! Opaque keyset-pagination cursors. Encodes a `(created_at_micros, sequence)`
+//! pair into a short URL-safe token via `sqids` so the notes listing can page
+//! forward with `WHERE (created_at, sequence) < (after_ts, after_seq)`
+//! instead of an `OFFSET` that drifts under concurrent inserts. Microsecond
+//! precision matches Postgres's `timestamptz` column, so rows that share a
+//! whole second (any quick burst of creates) still compare correctly.
+
+use sqids::Sqids;
+
+const ALPHABET: &str = "ngc4C1QXDulj9YsG5T2yv3hL7bUVrdkBaPxo8SZWiMtHANpKfwJm0IeOE6qzF";
+const MIN_LENGTH: u8 = 8;
+
+fn codec() -> Sqids {
+    Sqids::builder()
+        .alphabet(ALPHABET.chars().collect())
+        .min_length(MIN_LENGTH)
+        .build()
+        .expect("cursor alphabet must be valid")
+}
+
+/// Encodes a `(created_at_micros, sequence)` pair into an opaque cursor token.
+pub fn encode(created_at_micros: i64, sequence: i64) -> String {
+    codec().encode(&[created_at_micros as u64, sequence as u64]).unwrap_or_default()
+}
+
+/// Decodes a cursor token back into `(created_at_micros, sequence)`. Returns
+/// `None` for a malformed or tampered-with token rather than erroring, so
+/// callers can fall back to the first page.
+pub fn decode(cursor: &str) -> Option<(i64, i64)> {
+    let values = codec().decode(cursor);
+    match values.as_slice() {
+        [created_at, sequence] => Some((*created_at as i64, *sequence as i64)),
+        _ => None,
+    }
+}
@@ -0,0 +1,73 @@
+use axum::{Json, http::StatusCode, response::{IntoResponse, Response}};
+use serde::Serialize;
+
+/// A single field-level validation failure, e.g. `{"field": "title", "message": "must not be empty"}`.
+#[derive(Debug, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Crate-wide error type. Every fallible operation in a service or repository
+/// should resolve to one of these variants so handlers never have to parse
+/// error strings to pick a status code.
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("{0} not found")]
+    NotFound(&'static str),
+
+    #[error("invalid UUID: {0}")]
+    InvalidUuid(uuid::Error),
+
+    #[error("validation failed")]
+    Validation(Vec<FieldError>),
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    status: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<Vec<FieldError>>,
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status_code, message, errors) = match self {
+            AppError::NotFound(what) => (StatusCode::NOT_FOUND, format!("{} not found", what), None),
+            AppError::InvalidUuid(e) => (StatusCode::BAD_REQUEST, format!("Invalid UUID format: {}", e), None),
+            AppError::Validation(errors) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "validation failed".to_string(),
+                Some(errors),
+            ),
+            AppError::Unauthorized => (StatusCode::UNAUTHORIZED, "unauthorized".to_string(), None),
+            // Callers should pre-map `sqlx::Error::RowNotFound` to
+            // `AppError::NotFound("<entity>")` via their own `match` before it
+            // reaches here; this is just a generic backstop for anything that
+            // slips through uncaught, so it must not assume which entity.
+            AppError::Database(sqlx::Error::RowNotFound) => {
+                (StatusCode::NOT_FOUND, "resource not found".to_string(), None)
+            }
+            AppError::Database(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", e),
+                None,
+            ),
+        };
+
+        let body = ErrorBody {
+            status: "error",
+            message,
+            errors,
+        };
+
+        (status_code, Json(body)).into_response()
+    }
+}
@@ -10,28 +10,66 @@ use sqlx::postgres::PgPoolOptions;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+mod cursor;
+mod error;
 mod infrastructure;
 mod models;
 mod modules;
+mod state;
 
 use infrastructure::database::{PgPoolComponent, PgPoolComponentParameters, PgPoolProvider};
-use modules::notes::{AppState, NoteService, NotesModule, create_notes_router};
+use modules::attachments::{
+    AttachmentRepository, AttachmentService, AttachmentsModule, AttachmentRepositoryImpl,
+    AttachmentRepositoryImplParameters, NoopAttachmentRepository, routes::create_attachments_router,
+};
+use modules::auth::{
+    AuthModule, NoopUserRepository, UserRepository, UserRepositoryImplParameters, UserService,
+    routes::create_auth_router,
+};
+use modules::jobs::{JobQueueImpl, JobQueueImplParameters, JobsModule};
+use modules::jobs::handlers::NoteChangedHandler;
+use modules::jobs::worker::JobWorker;
+use modules::notes::cdc;
+use modules::notes::repository::NoteRepository;
+use modules::notes::{
+    AuditModule, AuditRepository, AuditRepositoryImpl, AuditRepositoryImplParameters, NoopAuditRepository,
+    NoteService, NotesModule, create_notes_router,
+};
+use state::AppState;
 use tower_http::cors::{Any, CorsLayer};
 
+#[cfg(feature = "sled-storage")]
+use modules::notes::sled_repository::{SledNoteRepositoryImpl, SledNoteRepositoryImplParameters, SledNotesModule};
+
 #[derive(OpenApi)]
 #[openapi(
     paths(
         modules::commons::handler::health,
         modules::notes::handler::get_list_note_handler,
         modules::notes::handler::create_note_handler,
-        modules::notes::handler::update_note_handler
+        modules::notes::handler::update_note_handler,
+        modules::notes::handler::delete_note_handler,
+        modules::notes::handler::get_note_history_handler,
+        modules::notes::handler::get_notes_stream_handler,
+        modules::attachments::handler::upload_attachment_handler,
+        modules::attachments::handler::get_attachment_handler,
+        modules::auth::handler::register_handler,
+        modules::auth::handler::login_handler,
+        modules::auth::handler::logout_handler
     ),
     components(schemas(
         models::model::NoteModel,
         models::model::NoteModelResponse,
+        models::model::AuditModel,
+        models::model::AttachmentModel,
+        models::model::AttachmentSummary,
+        models::model::UserModelResponse,
         modules::notes::CreateNoteSchema,
         modules::notes::UpdateNoteSchema,
-        modules::notes::FilterOptions
+        modules::notes::FilterOptions,
+        modules::notes::NotesPage,
+        modules::auth::RegisterSchema,
+        modules::auth::LoginSchema
     )),
     info(
         title = "Vibe Rust API",
@@ -47,47 +85,156 @@ use modules::commons::create_commons_router;
 async fn main() {
     dotenv().ok();
 
-    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let pool = match PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
-        .await
-    {
-        Ok(pool) => pool,
-        Err(e) => {
-            eprintln!("Failed to connect to database: {:?}", e);
-            std::process::exit(1);
-        }
-    };
+    let jwt_secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+    let jwt_expiry_minutes = std::env::var("JWT_EXPIRY_MINUTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60);
 
     let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::PUT])
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
         .allow_origin(Any)
         .allow_headers([CONTENT_TYPE]);
 
-    let pool = Arc::new(pool);
+    let storage_backend = std::env::var("STORAGE_BACKEND").unwrap_or_else(|_| "postgres".to_string());
+    let uses_sled = storage_backend == "sled" && cfg!(feature = "sled-storage");
 
-    let notes_module = NotesModule::builder()
-        .with_component_parameters::<PgPoolComponent>(PgPoolComponentParameters {
-            pool: Arc::clone(&pool),
-        })
-        .build();
+    // Postgres backs auth/jobs/audit/attachments/CDC regardless of which
+    // `NoteRepository` is storing notes, so it's only skipped when `sled` is
+    // actually selected — everything below degrades to a no-op instead.
+    let database_url = if uses_sled {
+        None
+    } else {
+        Some(std::env::var("DATABASE_URL").expect("DATABASE_URL must be set"))
+    };
+
+    let pool: Option<Arc<sqlx::PgPool>> = match &database_url {
+        Some(url) => match PgPoolOptions::new().max_connections(5).connect(url).await {
+            Ok(pool) => Some(Arc::new(pool)),
+            Err(e) => {
+                eprintln!("Failed to connect to database: {:?}", e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
 
-    let pool_provider: Arc<dyn PgPoolProvider> = notes_module
-        .provide()
-        .map(Arc::from)
-        .expect("PgPool provider must be available");
-    let _ = pool_provider.get_pool();
+    let note_repository: Arc<dyn NoteRepository> = match storage_backend.as_str() {
+        #[cfg(feature = "sled-storage")]
+        "sled" => {
+            let sled_path = std::env::var("SLED_PATH").unwrap_or_else(|_| "./data/notes.sled".to_string());
+            let db = Arc::new(sled::open(sled_path).expect("failed to open sled database"));
+
+            let sled_module = SledNotesModule::builder()
+                .with_component_parameters::<SledNoteRepositoryImpl>(SledNoteRepositoryImplParameters { db })
+                .build();
+
+            sled_module.resolve()
+        }
+        _ => {
+            let pool = pool.clone().expect("postgres pool required for the postgres storage backend");
+
+            let notes_module = NotesModule::builder()
+                .with_component_parameters::<PgPoolComponent>(PgPoolComponentParameters {
+                    pool: Arc::clone(&pool),
+                })
+                .with_component_parameters::<JobQueueImpl>(JobQueueImplParameters {
+                    pool: Arc::clone(&pool),
+                })
+                .build();
+
+            let pool_provider: Arc<dyn PgPoolProvider> = notes_module
+                .provide()
+                .map(Arc::from)
+                .expect("PgPool provider must be available");
+            let _ = pool_provider.get_pool();
+
+            notes_module.resolve()
+        }
+    };
+
+    let audit_repository: Arc<dyn AuditRepository> = match &pool {
+        Some(pool) => {
+            let audit_module = AuditModule::builder()
+                .with_component_parameters::<AuditRepositoryImpl>(AuditRepositoryImplParameters {
+                    pool: Arc::clone(pool),
+                })
+                .build();
+            audit_module.resolve()
+        }
+        None => Arc::new(NoopAuditRepository),
+    };
+
+    let attachment_repository: Arc<dyn AttachmentRepository> = match &pool {
+        Some(pool) => {
+            let attachments_module = AttachmentsModule::builder()
+                .with_component_parameters::<AttachmentRepositoryImpl>(AttachmentRepositoryImplParameters {
+                    pool: Arc::clone(pool),
+                })
+                .build();
+            attachments_module.resolve()
+        }
+        None => Arc::new(NoopAttachmentRepository),
+    };
+    let attachment_service = Arc::new(AttachmentService::new(Arc::clone(&attachment_repository)));
+
+    let note_service = Arc::new(NoteService::new(note_repository, audit_repository, attachment_repository));
+
+    if let Some(pool) = &pool {
+        let jobs_module = JobsModule::builder()
+            .with_component_parameters::<JobQueueImpl>(JobQueueImplParameters { pool: Arc::clone(pool) })
+            .build();
+        let job_queue = jobs_module.resolve();
+        let job_worker = JobWorker::new(job_queue).register("note.changed", Arc::new(NoteChangedHandler));
+        tokio::spawn(job_worker.run());
+    }
+
+    let user_repository: Arc<dyn UserRepository> = match &pool {
+        Some(pool) => {
+            let auth_module = AuthModule::builder()
+                .with_component_parameters::<modules::auth::UserRepositoryImpl>(UserRepositoryImplParameters {
+                    pool: Arc::clone(pool),
+                })
+                .build();
+            auth_module.resolve()
+        }
+        None => Arc::new(NoopUserRepository),
+    };
+    let user_service = Arc::new(UserService::new(user_repository, jwt_secret, jwt_expiry_minutes));
+
+    let (note_changes_tx, _) = tokio::sync::broadcast::channel(1024);
+
+    // The server's only graceful-shutdown trigger: a single `ctrl_c()`
+    // listener wired into `axum::serve`, which also drops the CDC
+    // replication slot first when CDC is running. Installing `ctrl_c()`
+    // anywhere else would steal SIGINT from this one and leave the server
+    // never shutting down.
+    let shutdown: std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> = match &database_url {
+        Some(url) => {
+            tokio::spawn(cdc::run(url.clone(), note_changes_tx.clone()));
+            Box::pin(cdc::drop_slot_on_shutdown(url.clone()))
+        }
+        None => Box::pin(async {
+            let _ = tokio::signal::ctrl_c().await;
+        }),
+    };
 
-    let note_service: Arc<dyn NoteService> = notes_module.resolve();
-    let app_state = Arc::new(AppState { note_service });
+    let app_state = Arc::new(AppState {
+        note_service,
+        user_service,
+        attachment_service,
+        note_changes: note_changes_tx,
+    });
 
     let api_docs = ApiDoc::openapi();
 
     let app = Router::new()
         .nest(
             "/api/v1",
-            create_commons_router().merge(create_notes_router(Arc::clone(&app_state))),
+            create_commons_router()
+                .merge(create_notes_router(Arc::clone(&app_state)))
+                .merge(create_auth_router(Arc::clone(&app_state)))
+                .merge(create_attachments_router(Arc::clone(&app_state))),
         )
         .layer(cors)
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api_docs));
@@ -96,6 +243,7 @@ async fn main() {
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await.unwrap();
     axum::serve(listener, app.into_make_service())
+        .with_graceful_shutdown(shutdown)
         .await
         .unwrap();
 }
@@ -8,6 +8,13 @@ pub struct NoteModel {
     pub title: String,
     pub content: String,
     pub is_published: bool,
+    pub owner_id: Option<String>,
+    /// Monotonically increasing insertion order, used to break ties on
+    /// `created_at` for keyset pagination.
+    pub sequence: i64,
+    /// Set when the note has been soft-deleted; excluded from listings and
+    /// `get_by_id` unless `include_deleted` is requested.
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -19,6 +26,72 @@ pub struct NoteModelResponse {
     pub title: String,
     pub content: String,
     pub is_published: bool,
+    pub owner_id: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub attachments: Vec<AttachmentSummary>,
+}
+
+/// A binary file attached to a note, stored inline as `bytea`. Decoded and
+/// thumbnailed (for images) at upload time so reads never re-process the
+/// original.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[allow(non_snake_case)]
+pub struct AttachmentModel {
+    pub id: String,
+    pub note_id: String,
+    pub filename: String,
+    pub content_type: String,
+    pub byte_size: i64,
+    #[serde(skip)]
+    pub data: Vec<u8>,
+    #[serde(skip)]
+    pub thumbnail: Option<Vec<u8>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Lightweight view of an attachment for embedding in `NoteModelResponse`;
+/// omits the binary payload.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[allow(non_snake_case)]
+pub struct AttachmentSummary {
+    pub id: String,
+    pub filename: String,
+    pub content_type: String,
+    pub byte_size: i64,
+    pub has_thumbnail: bool,
+}
+
+/// A registered user. The password hash is never serialized into API responses.
+#[derive(Debug, Clone, Deserialize, Serialize, sqlx::FromRow, ToSchema)]
+#[allow(non_snake_case)]
+pub struct UserModel {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserModelResponse {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+}
+
+/// A single append-only audit trail entry, recorded by `NoteService` on
+/// every create/update/delete.
+#[derive(Debug, Deserialize, Serialize, sqlx::FromRow, ToSchema)]
+#[allow(non_snake_case)]
+pub struct AuditModel {
+    pub id: String,
+    pub entity: String,
+    pub entity_id: String,
+    pub action: String,
+    pub actor_id: Option<String>,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Multipart, Path, Query, State},
+    http::header::CONTENT_TYPE,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+
+use crate::error::{AppError, FieldError};
+use crate::modules::auth::AuthUser;
+use crate::state::AppState;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct AttachmentVariantQuery {
+    /// Set to `thumb` to fetch the generated thumbnail instead of the original.
+    pub variant: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/notes/{id}/attachments",
+    tag = "Attachments",
+    params(
+        ("id" = String, Path, description = "Note ID to attach the file to")
+    ),
+    responses(
+        (status = 200, description = "Attachment uploaded successfully", body = serde_json::Value),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 422, description = "Missing or unreadable file part", body = serde_json::Value),
+        (status = 404, description = "Note not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+pub async fn upload_attachment_handler(
+    auth_user: AuthUser,
+    Path(note_id): Path<String>,
+    State(data): State<Arc<AppState>>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    uuid::Uuid::parse_str(&note_id).map_err(AppError::InvalidUuid)?;
+    data.note_service.ensure_note_owned(&note_id, Some(&auth_user.id)).await?;
+
+    let field = multipart
+        .next_field()
+        .await
+        .map_err(|e| {
+            AppError::Validation(vec![FieldError {
+                field: "file".to_string(),
+                message: e.to_string(),
+            }])
+        })?
+        .ok_or_else(|| {
+            AppError::Validation(vec![FieldError {
+                field: "file".to_string(),
+                message: "no file part provided".to_string(),
+            }])
+        })?;
+
+    let filename = field.file_name().unwrap_or("upload.bin").to_string();
+    let content_type = field.content_type().map(str::to_string);
+    let bytes = field
+        .bytes()
+        .await
+        .map_err(|e| {
+            AppError::Validation(vec![FieldError {
+                field: "file".to_string(),
+                message: e.to_string(),
+            }])
+        })?
+        .to_vec();
+
+    let attachment = data
+        .attachment_service
+        .upload(&note_id, &filename, content_type.as_deref(), bytes)
+        .await?;
+
+    let json_response = serde_json::json!({
+        "status": "OK",
+        "message": "Attachment uploaded successfully",
+        "data": attachment,
+    });
+
+    Ok(Json(json_response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/attachments/{id}",
+    tag = "Attachments",
+    params(
+        ("id" = String, Path, description = "Attachment ID"),
+        ("variant" = Option<String>, Query, description = "Set to `thumb` to fetch the generated thumbnail")
+    ),
+    responses(
+        (status = 200, description = "Attachment bytes", body = Vec<u8>),
+        (status = 404, description = "Attachment or thumbnail not found", body = serde_json::Value)
+    )
+)]
+pub async fn get_attachment_handler(
+    Path(id): Path<String>,
+    Query(query): Query<AttachmentVariantQuery>,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let (content_type, bytes) = data
+        .attachment_service
+        .get_variant(&id, query.variant.as_deref())
+        .await?;
+
+    Ok(([(CONTENT_TYPE, content_type)], bytes))
+}
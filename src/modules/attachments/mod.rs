@@ -0,0 +1,16 @@
+use shaku::module;
+
+pub mod handler;
+pub mod repository;
+pub mod routes;
+pub mod service;
+
+pub use repository::{AttachmentRepository, AttachmentRepositoryImpl, AttachmentRepositoryImplParameters, NoopAttachmentRepository};
+pub use service::AttachmentService;
+
+module! {
+    pub AttachmentsModule {
+        components = [repository::AttachmentRepositoryImpl],
+        providers = []
+    }
+}
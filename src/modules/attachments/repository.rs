@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Component, Interface};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::model::{AttachmentModel, AttachmentSummary};
+
+#[async_trait]
+pub trait AttachmentRepository: Interface + Send + Sync {
+    async fn create_attachment(
+        &self,
+        note_id: &str,
+        filename: &str,
+        content_type: &str,
+        data: Vec<u8>,
+        thumbnail: Option<Vec<u8>>,
+    ) -> Result<AttachmentModel, sqlx::Error>;
+
+    async fn get_by_id(&self, id: &str) -> Result<AttachmentModel, sqlx::Error>;
+
+    /// Binary-free summaries for embedding in `NoteModelResponse`.
+    async fn list_summaries_by_note(&self, note_id: &str) -> Result<Vec<AttachmentSummary>, sqlx::Error>;
+
+    /// Same as `list_summaries_by_note`, batched across many notes in a single
+    /// round-trip so listing endpoints don't pay one query per note.
+    async fn list_summaries_by_notes(
+        &self,
+        note_ids: &[String],
+    ) -> Result<HashMap<String, Vec<AttachmentSummary>>, sqlx::Error>;
+}
+
+#[derive(Component)]
+#[shaku(interface = AttachmentRepository)]
+pub struct AttachmentRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl AttachmentRepository for AttachmentRepositoryImpl {
+    async fn create_attachment(
+        &self,
+        note_id: &str,
+        filename: &str,
+        content_type: &str,
+        data: Vec<u8>,
+        thumbnail: Option<Vec<u8>>,
+    ) -> Result<AttachmentModel, sqlx::Error> {
+        let byte_size = data.len() as i64;
+
+        sqlx::query_as::<_, AttachmentModel>(
+            "INSERT INTO attachments (id, note_id, filename, content_type, byte_size, data, thumbnail, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+             RETURNING id, note_id, filename, content_type, byte_size, data, thumbnail, created_at",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(note_id)
+        .bind(filename)
+        .bind(content_type)
+        .bind(byte_size)
+        .bind(data)
+        .bind(thumbnail)
+        .fetch_one(&*self.pool)
+        .await
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<AttachmentModel, sqlx::Error> {
+        sqlx::query_as::<_, AttachmentModel>(
+            "SELECT id, note_id, filename, content_type, byte_size, data, thumbnail, created_at
+             FROM attachments
+             WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(&*self.pool)
+        .await
+    }
+
+    async fn list_summaries_by_note(&self, note_id: &str) -> Result<Vec<AttachmentSummary>, sqlx::Error> {
+        sqlx::query_as::<_, AttachmentSummary>(
+            "SELECT id, filename, content_type, byte_size, (thumbnail IS NOT NULL) AS has_thumbnail
+             FROM attachments
+             WHERE note_id = $1
+             ORDER BY created_at ASC",
+        )
+        .bind(note_id)
+        .fetch_all(&*self.pool)
+        .await
+    }
+
+    async fn list_summaries_by_notes(
+        &self,
+        note_ids: &[String],
+    ) -> Result<HashMap<String, Vec<AttachmentSummary>>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, AttachmentSummaryRow>(
+            "SELECT note_id, id, filename, content_type, byte_size, (thumbnail IS NOT NULL) AS has_thumbnail
+             FROM attachments
+             WHERE note_id = ANY($1)
+             ORDER BY created_at ASC",
+        )
+        .bind(note_ids)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        let mut by_note: HashMap<String, Vec<AttachmentSummary>> = HashMap::new();
+        for row in rows {
+            by_note.entry(row.note_id).or_default().push(AttachmentSummary {
+                id: row.id,
+                filename: row.filename,
+                content_type: row.content_type,
+                byte_size: row.byte_size,
+                has_thumbnail: row.has_thumbnail,
+            });
+        }
+
+        Ok(by_note)
+    }
+}
+
+/// Stand-in used when the process has no reachable Postgres (the `sled`
+/// storage backend with no `DATABASE_URL`). Attachments are stored as
+/// Postgres `bytea` today, so uploads fail cleanly and listings come back
+/// empty rather than the service failing to start.
+pub struct NoopAttachmentRepository;
+
+#[async_trait]
+impl AttachmentRepository for NoopAttachmentRepository {
+    async fn create_attachment(
+        &self,
+        _note_id: &str,
+        _filename: &str,
+        _content_type: &str,
+        _data: Vec<u8>,
+        _thumbnail: Option<Vec<u8>>,
+    ) -> Result<AttachmentModel, sqlx::Error> {
+        Err(sqlx::Error::Configuration(
+            "attachments are unavailable without a Postgres-backed storage backend".into(),
+        ))
+    }
+
+    async fn get_by_id(&self, _id: &str) -> Result<AttachmentModel, sqlx::Error> {
+        Err(sqlx::Error::RowNotFound)
+    }
+
+    async fn list_summaries_by_note(&self, _note_id: &str) -> Result<Vec<AttachmentSummary>, sqlx::Error> {
+        Ok(Vec::new())
+    }
+
+    async fn list_summaries_by_notes(
+        &self,
+        _note_ids: &[String],
+    ) -> Result<HashMap<String, Vec<AttachmentSummary>>, sqlx::Error> {
+        Ok(HashMap::new())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct AttachmentSummaryRow {
+    note_id: String,
+    id: String,
+    filename: String,
+    content_type: String,
+    byte_size: i64,
+    has_thumbnail: bool,
+}
@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::handler::{get_attachment_handler, upload_attachment_handler};
+
+pub fn create_attachments_router(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/notes/{id}/attachments", post(upload_attachment_handler))
+        .route("/attachments/{id}", get(get_attachment_handler))
+        .with_state(app_state)
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::models::model::AttachmentModel;
+
+use super::repository::AttachmentRepository;
+
+const THUMBNAIL_WIDTH: u32 = 200;
+
+pub struct AttachmentService {
+    repository: Arc<AttachmentRepository>,
+}
+
+impl AttachmentService {
+    pub fn new(repository: Arc<AttachmentRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Stores `data` against `note_id`, sniffing the content type from
+    /// `filename` when the client didn't send one, and generating a
+    /// fixed-width thumbnail when it decodes as an image.
+    pub async fn upload(
+        &self,
+        note_id: &str,
+        filename: &str,
+        content_type_hint: Option<&str>,
+        data: Vec<u8>,
+    ) -> Result<AttachmentModel, AppError> {
+        let content_type = content_type_hint
+            .map(str::to_string)
+            .unwrap_or_else(|| mime_guess::from_path(filename).first_or_octet_stream().to_string());
+
+        let thumbnail = if content_type.starts_with("image/") {
+            Self::make_thumbnail(&data)
+        } else {
+            None
+        };
+
+        Ok(self
+            .repository
+            .create_attachment(note_id, filename, &content_type, data, thumbnail)
+            .await?)
+    }
+
+    fn make_thumbnail(data: &[u8]) -> Option<Vec<u8>> {
+        let image = image::load_from_memory(data).ok()?;
+        // Unbounded height: the width is always `THUMBNAIL_WIDTH` and the
+        // height follows the original's aspect ratio.
+        let thumbnail = image.thumbnail(THUMBNAIL_WIDTH, u32::MAX);
+
+        let mut buf = Vec::new();
+        thumbnail
+            .write_to(&mut std::io::Cursor::new(&mut buf), image::ImageFormat::Png)
+            .ok()?;
+
+        Some(buf)
+    }
+
+    /// Returns `(content_type, bytes)` for the attachment, or its thumbnail
+    /// when `variant` is `Some("thumb")`.
+    pub async fn get_variant(&self, id: &str, variant: Option<&str>) -> Result<(String, Vec<u8>), AppError> {
+        let attachment = self.repository.get_by_id(id).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => AppError::NotFound("attachment"),
+            e => AppError::Database(e),
+        })?;
+
+        match variant {
+            Some("thumb") => {
+                let thumbnail = attachment.thumbnail.ok_or(AppError::NotFound("thumbnail"))?;
+                Ok(("image/png".to_string(), thumbnail))
+            }
+            _ => Ok((attachment.content_type, attachment.data)),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum_extra::extract::CookieJar;
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// The currently-authenticated user, resolved from the `token` cookie or an
+/// `Authorization: Bearer` header. Handlers that need a logged-in user take
+/// this as an extractor argument instead of parsing the request themselves.
+pub struct AuthUser {
+    pub id: String,
+}
+
+impl FromRequestParts<Arc<AppState>> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let token = if let Ok(jar) = CookieJar::from_request_parts(parts, state).await {
+            jar.get("token").map(|c| c.value().to_string())
+        } else {
+            None
+        };
+
+        let token = token
+            .or_else(|| {
+                parts
+                    .headers
+                    .get(axum::http::header::AUTHORIZATION)?
+                    .to_str()
+                    .ok()?
+                    .strip_prefix("Bearer ")
+                    .map(str::to_string)
+            })
+            .ok_or(AppError::Unauthorized)?;
+
+        let claims = state.user_service.validate_token(&token)?;
+
+        Ok(AuthUser { id: claims.sub })
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State, response::IntoResponse};
+use axum_extra::extract::{
+    CookieJar,
+    cookie::{Cookie, SameSite},
+};
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+use super::{LoginSchema, RegisterSchema};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/register",
+    tag = "Auth",
+    request_body = RegisterSchema,
+    responses(
+        (status = 200, description = "User registered successfully", body = serde_json::Value),
+        (status = 422, description = "Validation failed", body = serde_json::Value)
+    )
+)]
+pub async fn register_handler(
+    State(data): State<Arc<AppState>>,
+    Json(payload): Json<RegisterSchema>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = data.user_service.register(payload).await?;
+
+    let json_response = serde_json::json!({
+        "status": "OK",
+        "message": "User registered successfully",
+        "data": user,
+    });
+
+    Ok(Json(json_response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    tag = "Auth",
+    request_body = LoginSchema,
+    responses(
+        (status = 200, description = "Login successful", body = serde_json::Value),
+        (status = 401, description = "Invalid credentials", body = serde_json::Value)
+    )
+)]
+pub async fn login_handler(
+    State(data): State<Arc<AppState>>,
+    jar: CookieJar,
+    Json(payload): Json<LoginSchema>,
+) -> Result<impl IntoResponse, AppError> {
+    let (user, token) = data.user_service.login(payload).await?;
+
+    let cookie = Cookie::build(("token", token))
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .path("/")
+        .build();
+
+    let json_response = serde_json::json!({
+        "status": "OK",
+        "message": "Login successful",
+        "data": user,
+    });
+
+    Ok((jar.add(cookie), Json(json_response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout",
+    tag = "Auth",
+    responses(
+        (status = 200, description = "Logout successful", body = serde_json::Value)
+    )
+)]
+pub async fn logout_handler(jar: CookieJar) -> impl IntoResponse {
+    let jar = jar.remove(Cookie::from("token"));
+
+    let json_response = serde_json::json!({
+        "status": "OK",
+        "message": "Logout successful",
+    });
+
+    (jar, Json(json_response))
+}
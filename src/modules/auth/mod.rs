@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use shaku::module;
+use utoipa::ToSchema;
+
+pub mod extractor;
+pub mod handler;
+pub mod repository;
+pub mod routes;
+pub mod service;
+
+pub use extractor::AuthUser;
+pub use repository::{NoopUserRepository, UserRepository, UserRepositoryImpl, UserRepositoryImplParameters};
+pub use service::UserService;
+
+module! {
+    pub AuthModule {
+        components = [repository::UserRepositoryImpl],
+        providers = []
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct RegisterSchema {
+    pub name: String,
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct LoginSchema {
+    pub email: String,
+    pub password: String,
+}
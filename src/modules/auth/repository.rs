@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Component, Interface};
+use sqlx::PgPool;
+
+use crate::models::model::UserModel;
+
+#[async_trait]
+pub trait UserRepository: Interface + Send + Sync {
+    async fn create_user(
+        &self,
+        id: &str,
+        name: &str,
+        email: &str,
+        password_hash: &str,
+    ) -> Result<UserModel, sqlx::Error>;
+    async fn get_by_email(&self, email: &str) -> Result<UserModel, sqlx::Error>;
+    async fn get_by_id(&self, id: &str) -> Result<UserModel, sqlx::Error>;
+}
+
+#[derive(Component)]
+#[shaku(interface = UserRepository)]
+pub struct UserRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl UserRepository for UserRepositoryImpl {
+    async fn create_user(
+        &self,
+        id: &str,
+        name: &str,
+        email: &str,
+        password_hash: &str,
+    ) -> Result<UserModel, sqlx::Error> {
+        sqlx::query_as::<_, UserModel>(
+            "
+        INSERT INTO users (
+            id,
+            name,
+            email,
+            password_hash
+        ) VALUES ($1, $2, $3, $4)
+        RETURNING
+            id,
+            name,
+            email,
+            password_hash,
+            created_at
+        ",
+        )
+        .bind(id)
+        .bind(name)
+        .bind(email)
+        .bind(password_hash)
+        .fetch_one(&*self.pool)
+        .await
+    }
+
+    async fn get_by_email(&self, email: &str) -> Result<UserModel, sqlx::Error> {
+        // `email` has no uniqueness constraint in this schema (only `name`
+        // does), so more than one row can match. Pick the oldest account
+        // deterministically rather than whichever row Postgres happens to
+        // return first.
+        sqlx::query_as::<_, UserModel>(
+            "SELECT id, name, email, password_hash, created_at FROM users
+             WHERE email = $1
+             ORDER BY created_at ASC
+             LIMIT 1",
+        )
+        .bind(email)
+        .fetch_one(&*self.pool)
+        .await
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<UserModel, sqlx::Error> {
+        sqlx::query_as::<_, UserModel>(
+            "SELECT id, name, email, password_hash, created_at FROM users WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(&*self.pool)
+        .await
+    }
+}
+
+/// Stand-in used when the process has no reachable Postgres (the `sled`
+/// storage backend with no `DATABASE_URL`). User accounts are stored in
+/// Postgres today, so registration/login fail cleanly rather than the
+/// service failing to start; since no account can ever be created, no token
+/// can be minted, so every `AuthUser`-gated endpoint stays correctly locked.
+pub struct NoopUserRepository;
+
+#[async_trait]
+impl UserRepository for NoopUserRepository {
+    async fn create_user(
+        &self,
+        _id: &str,
+        _name: &str,
+        _email: &str,
+        _password_hash: &str,
+    ) -> Result<UserModel, sqlx::Error> {
+        Err(sqlx::Error::Configuration(
+            "auth is unavailable without a Postgres-backed storage backend".into(),
+        ))
+    }
+
+    async fn get_by_email(&self, _email: &str) -> Result<UserModel, sqlx::Error> {
+        Err(sqlx::Error::RowNotFound)
+    }
+
+    async fn get_by_id(&self, _id: &str) -> Result<UserModel, sqlx::Error> {
+        Err(sqlx::Error::RowNotFound)
+    }
+}
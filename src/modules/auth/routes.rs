@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::post};
+
+use crate::state::AppState;
+
+use super::handler::{login_handler, logout_handler, register_handler};
+
+pub fn create_auth_router(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/auth/register", post(register_handler))
+        .route("/auth/login", post(login_handler))
+        .route("/auth/logout", post(logout_handler))
+        .with_state(app_state)
+}
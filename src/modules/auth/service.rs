@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use argon2::{
+    Argon2,
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::model::{UserModel, UserModelResponse};
+
+use super::{LoginSchema, RegisterSchema, repository::UserRepository};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub exp: i64,
+}
+
+pub struct UserService {
+    repository: Arc<dyn UserRepository>,
+    jwt_secret: String,
+    jwt_expiry_minutes: i64,
+}
+
+impl UserService {
+    pub fn new(repository: Arc<dyn UserRepository>, jwt_secret: String, jwt_expiry_minutes: i64) -> Self {
+        Self {
+            repository,
+            jwt_secret,
+            jwt_expiry_minutes,
+        }
+    }
+
+    pub async fn register(&self, data: RegisterSchema) -> Result<UserModelResponse, AppError> {
+        let salt = SaltString::generate(&mut OsRng);
+        let password_hash = Argon2::default()
+            .hash_password(data.password.as_bytes(), &salt)
+            .map_err(|_| {
+                AppError::Validation(vec![crate::error::FieldError {
+                    field: "password".to_string(),
+                    message: "could not hash password".to_string(),
+                }])
+            })?
+            .to_string();
+
+        let id = Uuid::new_v4().to_string();
+        let user = self
+            .repository
+            .create_user(&id, &data.name, &data.email, &password_hash)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                    // `name` is the only column the `users` table actually
+                    // constrains unique; only trust the error as an `email`
+                    // conflict if the failing constraint is named for it.
+                    let field = match db_err.constraint() {
+                        Some(c) if c.contains("email") => "email",
+                        _ => "name",
+                    };
+                    AppError::Validation(vec![crate::error::FieldError {
+                        field: field.to_string(),
+                        message: format!("{} is already in use", field),
+                    }])
+                }
+                e => AppError::Database(e),
+            })?;
+
+        Ok(Self::to_user_response(&user))
+    }
+
+    pub async fn login(&self, data: LoginSchema) -> Result<(UserModelResponse, String), AppError> {
+        let user = self
+            .repository
+            .get_by_email(&data.email)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => AppError::Unauthorized,
+                e => AppError::Database(e),
+            })?;
+
+        let parsed_hash = PasswordHash::new(&user.password_hash)
+            .map_err(|_| AppError::Unauthorized)?;
+        Argon2::default()
+            .verify_password(data.password.as_bytes(), &parsed_hash)
+            .map_err(|_| AppError::Unauthorized)?;
+
+        let token = self.issue_token(&user.id)?;
+        Ok((Self::to_user_response(&user), token))
+    }
+
+    pub async fn get_by_id(&self, id: &str) -> Result<UserModelResponse, AppError> {
+        let user = self.repository.get_by_id(id).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => AppError::NotFound("user"),
+            e => AppError::Database(e),
+        })?;
+        Ok(Self::to_user_response(&user))
+    }
+
+    pub fn validate_token(&self, token: &str) -> Result<Claims, AppError> {
+        decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret.as_bytes()),
+            &Validation::default(),
+        )
+        .map(|data| data.claims)
+        .map_err(|_| AppError::Unauthorized)
+    }
+
+    fn issue_token(&self, user_id: &str) -> Result<String, AppError> {
+        let exp = (Utc::now() + Duration::minutes(self.jwt_expiry_minutes)).timestamp();
+        let claims = Claims {
+            sub: user_id.to_string(),
+            exp,
+        };
+
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.jwt_secret.as_bytes()),
+        )
+        .map_err(|_| AppError::Unauthorized)
+    }
+
+    fn to_user_response(user: &UserModel) -> UserModelResponse {
+        UserModelResponse {
+            id: user.id.clone(),
+            name: user.name.clone(),
+            email: user.email.clone(),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod handler;
+pub mod routes;
+
+pub use routes::create_commons_router;
@@ -0,0 +1,18 @@
+//! Built-in handlers registered against the worker in `main()`.
+
+use async_trait::async_trait;
+
+use super::worker::JobHandler;
+
+/// Placeholder for the outbound webhook / search re-indexing side effects
+/// triggered by `note.changed`. Swap this out for a real HTTP client or
+/// search indexer once those integrations exist.
+pub struct NoteChangedHandler;
+
+#[async_trait]
+impl JobHandler for NoteChangedHandler {
+    async fn handle(&self, payload: &serde_json::Value) -> Result<(), String> {
+        println!("dispatching note.changed side effects for payload: {}", payload);
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use shaku::module;
+
+pub mod handlers;
+pub mod repository;
+pub mod worker;
+
+pub use repository::{JobQueueImpl, JobQueueImplParameters};
+pub use worker::JobHandler;
+
+module! {
+    pub JobsModule {
+        components = [repository::JobQueueImpl],
+        providers = []
+    }
+}
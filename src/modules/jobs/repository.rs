@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use shaku::{Component, Interface};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// A row in the `jobs` table. `status` is one of `pending`, `running`
+/// (claimed by a worker), `done`, or `dead`.
+#[derive(Debug, Clone, Deserialize, Serialize, sqlx::FromRow)]
+pub struct JobModel {
+    pub id: String,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub run_at: chrono::DateTime<chrono::Utc>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+}
+
+const MAX_ATTEMPTS: i32 = 8;
+
+#[async_trait]
+pub trait JobQueue: Interface + Send + Sync {
+    /// Enqueues `kind`/`payload` to run immediately, as part of an
+    /// in-progress transaction so the job and the caller's own write commit
+    /// or roll back together.
+    async fn enqueue_in_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        kind: &str,
+        payload: serde_json::Value,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Enqueues `kind`/`payload` to run immediately, outside any caller
+    /// transaction.
+    async fn enqueue(&self, kind: &str, payload: serde_json::Value) -> Result<(), sqlx::Error>;
+
+    /// Atomically claims up to `limit` due jobs via `FOR UPDATE SKIP LOCKED`
+    /// so multiple worker instances never process the same job twice.
+    async fn claim_due_jobs(&self, limit: i32) -> Result<Vec<JobModel>, sqlx::Error>;
+
+    async fn mark_done(&self, id: &str) -> Result<(), sqlx::Error>;
+
+    /// Reschedules `id` with exponential backoff, or moves it to the dead
+    /// letter state once this failure brings the attempt count
+    /// (`attempts + 1`) to `MAX_ATTEMPTS`.
+    async fn reschedule_or_dead_letter(&self, id: &str, attempts: i32, error: &str) -> Result<(), sqlx::Error>;
+}
+
+#[derive(Component)]
+#[shaku(interface = JobQueue)]
+pub struct JobQueueImpl {
+    pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl JobQueue for JobQueueImpl {
+    async fn enqueue_in_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        kind: &str,
+        payload: serde_json::Value,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO jobs (id, kind, payload, status, run_at, attempts) VALUES ($1, $2, $3, 'pending', NOW(), 0)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(kind)
+        .bind(payload)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn enqueue(&self, kind: &str, payload: serde_json::Value) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO jobs (id, kind, payload, status, run_at, attempts) VALUES ($1, $2, $3, 'pending', NOW(), 0)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(kind)
+        .bind(payload)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn claim_due_jobs(&self, limit: i32) -> Result<Vec<JobModel>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let jobs = sqlx::query_as::<_, JobModel>(
+            "SELECT id, kind, payload, status, run_at, attempts, last_error
+             FROM jobs
+             WHERE status = 'pending' AND run_at <= NOW()
+             ORDER BY run_at
+             LIMIT $1
+             FOR UPDATE SKIP LOCKED",
+        )
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if !jobs.is_empty() {
+            let ids: Vec<&str> = jobs.iter().map(|j| j.id.as_str()).collect();
+            sqlx::query("UPDATE jobs SET status = 'running' WHERE id = ANY($1)")
+                .bind(&ids as &[&str])
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(jobs)
+    }
+
+    async fn mark_done(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE jobs SET status = 'done' WHERE id = $1")
+            .bind(id)
+            .execute(&*self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn reschedule_or_dead_letter(&self, id: &str, attempts: i32, error: &str) -> Result<(), sqlx::Error> {
+        let next_attempts = attempts + 1;
+
+        if next_attempts >= MAX_ATTEMPTS {
+            sqlx::query("UPDATE jobs SET status = 'dead', attempts = $2, last_error = $3 WHERE id = $1")
+                .bind(id)
+                .bind(next_attempts)
+                .bind(error)
+                .execute(&*self.pool)
+                .await?;
+            return Ok(());
+        }
+
+        let backoff_seconds = 2_i64.pow(next_attempts as u32);
+        sqlx::query(
+            "UPDATE jobs
+             SET status = 'pending', attempts = $2, last_error = $3,
+                 run_at = NOW() + ($4 || ' seconds')::interval
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(next_attempts)
+        .bind(error)
+        .bind(backoff_seconds.to_string())
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
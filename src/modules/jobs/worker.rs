@@ -0,0 +1,77 @@
+//! Polls the `jobs` table for due work and dispatches it to a handler
+//! registered by `kind`. Spawned once from `main()`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::repository::{JobModel, JobQueue};
+
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    async fn handle(&self, payload: &serde_json::Value) -> Result<(), String>;
+}
+
+pub struct JobWorker {
+    queue: Arc<dyn JobQueue>,
+    handlers: HashMap<String, Arc<dyn JobHandler>>,
+    poll_interval: Duration,
+    batch_size: i32,
+}
+
+impl JobWorker {
+    pub fn new(queue: Arc<dyn JobQueue>) -> Self {
+        Self {
+            queue,
+            handlers: HashMap::new(),
+            poll_interval: Duration::from_secs(2),
+            batch_size: 10,
+        }
+    }
+
+    pub fn register(mut self, kind: &str, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers.insert(kind.to_string(), handler);
+        self
+    }
+
+    pub async fn run(self) {
+        loop {
+            match self.queue.claim_due_jobs(self.batch_size).await {
+                Ok(jobs) => {
+                    for job in jobs {
+                        self.dispatch(job).await;
+                    }
+                }
+                Err(e) => eprintln!("job queue poll failed: {:?}", e),
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    async fn dispatch(&self, job: JobModel) {
+        let Some(handler) = self.handlers.get(&job.kind) else {
+            eprintln!("no handler registered for job kind '{}'", job.kind);
+            let _ = self
+                .queue
+                .reschedule_or_dead_letter(&job.id, job.attempts, "no handler registered")
+                .await;
+            return;
+        };
+
+        match handler.handle(&job.payload).await {
+            Ok(()) => {
+                if let Err(e) = self.queue.mark_done(&job.id).await {
+                    eprintln!("failed to mark job {} done: {:?}", job.id, e);
+                }
+            }
+            Err(e) => {
+                if let Err(db_err) = self.queue.reschedule_or_dead_letter(&job.id, job.attempts, &e).await {
+                    eprintln!("failed to reschedule job {}: {:?}", job.id, db_err);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod attachments;
+pub mod auth;
+pub mod commons;
+pub mod jobs;
+pub mod notes;
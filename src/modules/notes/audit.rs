@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shaku::{Component, Interface};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::model::AuditModel;
+
+#[async_trait]
+pub trait AuditRepository: Interface + Send + Sync {
+    /// Appends a row to the `audit` table. `before`/`after` are the
+    /// `NoteModel` snapshots as JSON, with `None` standing in for "did not
+    /// exist yet" (the `before` of a create).
+    async fn record(
+        &self,
+        entity: &str,
+        entity_id: &str,
+        action: &str,
+        actor_id: Option<&str>,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Returns the ordered audit trail for `entity_id`, oldest first.
+    async fn get_history(&self, entity_id: &str) -> Result<Vec<AuditModel>, sqlx::Error>;
+}
+
+#[derive(Component)]
+#[shaku(interface = AuditRepository)]
+pub struct AuditRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl AuditRepository for AuditRepositoryImpl {
+    async fn record(
+        &self,
+        entity: &str,
+        entity_id: &str,
+        action: &str,
+        actor_id: Option<&str>,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO audit (id, entity, entity_id, action, actor_id, before, after, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(entity)
+        .bind(entity_id)
+        .bind(action)
+        .bind(actor_id)
+        .bind(before)
+        .bind(after)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_history(&self, entity_id: &str) -> Result<Vec<AuditModel>, sqlx::Error> {
+        sqlx::query_as::<_, AuditModel>(
+            "SELECT id, entity, entity_id, action, actor_id, before, after, created_at
+             FROM audit
+             WHERE entity_id = $1
+             ORDER BY created_at ASC",
+        )
+        .bind(entity_id)
+        .fetch_all(&*self.pool)
+        .await
+    }
+}
+
+/// Stand-in used when the process has no reachable Postgres (the `sled`
+/// storage backend with no `DATABASE_URL`). The audit trail is a
+/// Postgres-only feature today, so recording is a silent no-op and history
+/// is always empty rather than the service failing to start.
+pub struct NoopAuditRepository;
+
+#[async_trait]
+impl AuditRepository for NoopAuditRepository {
+    async fn record(
+        &self,
+        _entity: &str,
+        _entity_id: &str,
+        _action: &str,
+        _actor_id: Option<&str>,
+        _before: Option<serde_json::Value>,
+        _after: Option<serde_json::Value>,
+    ) -> Result<(), sqlx::Error> {
+        Ok(())
+    }
+
+    async fn get_history(&self, _entity_id: &str) -> Result<Vec<AuditModel>, sqlx::Error> {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,257 @@
+//! Change-data-capture: streams note inserts/updates/deletes out of Postgres
+//! logical replication (`pgoutput`) and fans them out over a broadcast
+//! channel so `GET /api/v1/notes/stream` can relay them as SSE.
+
+use std::collections::HashMap;
+
+use futures::StreamExt;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tokio_postgres::replication::LogicalReplicationStream;
+use tokio_postgres::types::PgLsn;
+use tokio_postgres::{Client, NoTls};
+use utoipa::ToSchema;
+
+use crate::models::model::NoteModel;
+
+const PUBLICATION_NAME: &str = "notes_pub";
+const SLOT_NAME: &str = "notes_cdc_slot";
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NoteChangeEvent {
+    pub op: ChangeOp,
+    pub note: NoteModel,
+}
+
+/// Ensures the `notes_pub` publication and `notes_cdc_slot` replication slot
+/// exist, then opens a dedicated replication connection and streams decoded
+/// `pgoutput` messages onto `tx`. Whenever the stream ends or fails it
+/// reconnects after 5 seconds, so this never returns.
+pub async fn run(database_url: String, tx: broadcast::Sender<NoteChangeEvent>) {
+    loop {
+        if let Err(e) = replicate_once(&database_url, &tx).await {
+            eprintln!("CDC replication stream ended: {:?}; retrying in 5s", e);
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    }
+}
+
+/// Waits for a Ctrl-C/SIGINT and drops `notes_cdc_slot` before returning, so a
+/// graceful shutdown doesn't leave a permanent slot behind accumulating WAL.
+/// A crash or `kill -9` still leaks the slot — that case needs an external
+/// monitor (e.g. alert on `pg_replication_slots.active = false`), which is
+/// out of scope for this process.
+pub async fn drop_slot_on_shutdown(database_url: String) {
+    if tokio::signal::ctrl_c().await.is_err() {
+        return;
+    }
+
+    if let Err(e) = drop_replication_slot(&database_url, SLOT_NAME).await {
+        eprintln!("failed to drop CDC replication slot on shutdown: {:?}", e);
+    }
+}
+
+async fn drop_replication_slot(
+    database_url: &str,
+    slot_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let (client, connection) = tokio_postgres::connect(database_url, NoTls).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("CDC slot-teardown connection error: {:?}", e);
+        }
+    });
+
+    client
+        .query(
+            "SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots WHERE slot_name = $1",
+            &[&slot_name],
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn replicate_once(
+    database_url: &str,
+    tx: &broadcast::Sender<NoteChangeEvent>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let (client, connection) = tokio_postgres::connect(database_url, NoTls).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("CDC control connection error: {:?}", e);
+        }
+    });
+
+    ensure_publication(&client).await?;
+    ensure_replication_slot(database_url, SLOT_NAME).await?;
+
+    let repl_conn_str = format!("{} replication=database", database_url);
+    let (repl_client, repl_connection) = tokio_postgres::connect(&repl_conn_str, NoTls).await?;
+    tokio::spawn(async move {
+        if let Err(e) = repl_connection.await {
+            eprintln!("CDC replication connection error: {:?}", e);
+        }
+    });
+
+    let query = format!(
+        "START_REPLICATION SLOT {} LOGICAL 0/0 (proto_version '1', publication_names '{}')",
+        SLOT_NAME, PUBLICATION_NAME
+    );
+    let copy_stream = repl_client.copy_both_simple::<bytes::Bytes>(&query).await?;
+    let mut stream = std::pin::pin!(LogicalReplicationStream::new(copy_stream));
+
+    let mut relations: HashMap<u32, Vec<String>> = HashMap::new();
+    let mut last_flush_lsn = PgLsn::from(0);
+
+    while let Some(message) = stream.next().await {
+        match message? {
+            tokio_postgres::replication::ReplicationMessage::XLogData(body) => {
+                last_flush_lsn = PgLsn::from(body.wal_end());
+                if let Some(event) = decode_message(body.into_data(), &mut relations) {
+                    let _ = tx.send(event);
+                }
+            }
+            tokio_postgres::replication::ReplicationMessage::PrimaryKeepAlive(keepalive) => {
+                if keepalive.reply() == 1 {
+                    send_standby_status_update(&mut stream, last_flush_lsn).await?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// `CREATE PUBLICATION notes_pub FOR TABLE notes` if it doesn't already exist.
+async fn ensure_publication(client: &Client) -> Result<(), tokio_postgres::Error> {
+    let exists: bool = client
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM pg_publication WHERE pubname = $1)",
+            &[&PUBLICATION_NAME],
+        )
+        .await?
+        .get(0);
+
+    if !exists {
+        client
+            .batch_execute(&format!("CREATE PUBLICATION {} FOR TABLE notes", PUBLICATION_NAME))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Creates the logical replication slot (using the `pgoutput` plugin) on a
+/// dedicated replication-mode connection if it doesn't already exist.
+async fn ensure_replication_slot(
+    database_url: &str,
+    slot_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let repl_conn_str = format!("{} replication=database", database_url);
+    let (client, connection) = tokio_postgres::connect(&repl_conn_str, NoTls).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("CDC slot-setup connection error: {:?}", e);
+        }
+    });
+
+    let exists: bool = client
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM pg_replication_slots WHERE slot_name = $1)",
+            &[&slot_name],
+        )
+        .await?
+        .get(0);
+
+    if !exists {
+        client
+            .simple_query(&format!(
+                "CREATE_REPLICATION_SLOT {} LOGICAL pgoutput",
+                slot_name
+            ))
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn send_standby_status_update(
+    stream: &mut std::pin::Pin<&mut LogicalReplicationStream>,
+    lsn: PgLsn,
+) -> Result<(), tokio_postgres::Error> {
+    let lsn_val: u64 = lsn.into();
+    stream
+        .as_mut()
+        .standby_status_update(
+            PgLsn::from(lsn_val),
+            PgLsn::from(lsn_val),
+            PgLsn::from(lsn_val),
+            std::time::SystemTime::now(),
+            0,
+        )
+        .await
+}
+
+/// Reconstructs a row's columns from the preceding `Relation` message and
+/// turns `Insert`/`Update`/`Delete` into a `NoteChangeEvent`. `Begin`/`Commit`
+/// carry no row data and are dropped.
+fn decode_message(
+    data: tokio_postgres::replication::LogicalReplicationMessage,
+    relations: &mut HashMap<u32, Vec<String>>,
+) -> Option<NoteChangeEvent> {
+    use tokio_postgres::replication::LogicalReplicationMessage::*;
+    use tokio_postgres::replication::TupleData;
+
+    let to_note = |rel_id: u32, tuple: &[TupleData], relations: &HashMap<u32, Vec<String>>| {
+        let columns = relations.get(&rel_id)?;
+        let mut fields: HashMap<&str, String> = HashMap::new();
+        for (name, value) in columns.iter().zip(tuple.iter()) {
+            if let TupleData::Text(bytes) = value {
+                fields.insert(name.as_str(), String::from_utf8_lossy(bytes).to_string());
+            }
+        }
+        Some(NoteModel {
+            id: fields.remove("id").unwrap_or_default(),
+            title: fields.remove("title").unwrap_or_default(),
+            content: fields.remove("content").unwrap_or_default(),
+            is_published: fields.remove("is_published").map(|v| v == "t").unwrap_or_default(),
+            owner_id: fields.remove("owner_id"),
+            sequence: fields.remove("sequence").and_then(|v| v.parse().ok()).unwrap_or_default(),
+            deleted_at: None,
+            created_at: None,
+            updated_at: None,
+        })
+    };
+
+    match data {
+        Relation(body) => {
+            let columns = body.columns().iter().map(|c| c.name().to_string()).collect();
+            relations.insert(body.rel_id(), columns);
+            None
+        }
+        Insert(body) => to_note(body.rel_id(), body.tuple().tuple_data(), relations)
+            .map(|note| NoteChangeEvent { op: ChangeOp::Insert, note }),
+        Update(body) => body
+            .new_tuple()
+            .tuple_data()
+            .first()
+            .and(to_note(body.rel_id(), body.new_tuple().tuple_data(), relations))
+            .map(|note| NoteChangeEvent { op: ChangeOp::Update, note }),
+        Delete(body) => body
+            .key_tuple()
+            .or(body.old_tuple())
+            .and_then(|tuple| to_note(body.rel_id(), tuple.tuple_data(), relations))
+            .map(|note| NoteChangeEvent { op: ChangeOp::Delete, note }),
+        _ => None,
+    }
+}
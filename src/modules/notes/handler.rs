@@ -1,21 +1,32 @@
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
+use futures::stream::Stream;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt as _;
 
-use super::{AppState, CreateNoteSchema, FilterOptions, UpdateNoteSchema};
+use crate::error::AppError;
+use crate::modules::auth::AuthUser;
+use crate::state::AppState;
+
+use super::{CreateNoteSchema, FilterOptions, UpdateNoteSchema};
 
 #[utoipa::path(
     get,
     path = "/api/v1/notes",
     tag = "Notes",
     params(
-        ("limit" = Option<i32>, Query, description = "Limit number of notes returned"),
-        ("page" = Option<i32>, Query, description = "Page number for pagination")
+        ("limit" = Option<i32>, Query, description = "Limit number of notes returned (max 100)"),
+        ("after" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
     ),
     responses(
         (status = 200, description = "Notes retrieved successfully", body = serde_json::Value),
@@ -25,22 +36,16 @@ use super::{AppState, CreateNoteSchema, FilterOptions, UpdateNoteSchema};
 pub async fn get_list_note_handler(
     Query(opts): Query<FilterOptions>,
     State(data): State<Arc<AppState>>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let note_service = data.note_service.clone();
 
-    let notes = note_service.get_notes(opts).await.map_err(|e| {
-        let error_response = serde_json::json!({
-            "status": "error",
-            "message": e,
-        });
-
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
-    })?;
+    let page = note_service.get_notes(opts).await?;
 
     let json_response = serde_json::json!({
         "status": "OK",
         "message": "Notes retrieved successfully",
-        "data": notes,
+        "data": page.data,
+        "next_cursor": page.next_cursor,
     });
 
     Ok(Json(json_response))
@@ -57,18 +62,13 @@ pub async fn get_list_note_handler(
     )
 )]
 pub async fn create_note_handler(
+    auth_user: AuthUser,
     State(data): State<Arc<AppState>>,
     Json(note): Json<CreateNoteSchema>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let note_service = data.note_service.clone();
 
-    let created_note = note_service.create_note(note).await.map_err(|e| {
-        let error_response = serde_json::json!({
-            "status": "error",
-            "message": e,
-        });
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
-    })?;
+    let created_note = note_service.create_note(note, Some(&auth_user.id)).await?;
 
     let json_response = serde_json::json!({
         "status": "OK",
@@ -94,24 +94,14 @@ pub async fn create_note_handler(
     )
 )]
 pub async fn update_note_handler(
+    auth_user: AuthUser,
     Path(id): Path<String>,
     State(data): State<Arc<AppState>>,
     Json(note): Json<UpdateNoteSchema>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let note_service = data.note_service.clone();
 
-    let updated_note = note_service.update_note(id, note).await.map_err(|e| {
-        let error_response = serde_json::json!({
-            "status": "error",
-            "message": e,
-        });
-
-        if e.contains("Invalid UUID format") {
-            (StatusCode::BAD_REQUEST, Json(error_response))
-        } else {
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
-        }
-    })?;
+    let updated_note = note_service.update_note(id, note, Some(&auth_user.id)).await?;
 
     let json_response = serde_json::json!({
         "status": "OK",
@@ -121,3 +111,90 @@ pub async fn update_note_handler(
 
     Ok(Json(json_response))
 }
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/notes/{id}",
+    tag = "Notes",
+    params(
+        ("id" = String, Path, description = "Note ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Note soft-deleted successfully", body = serde_json::Value),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 404, description = "Note not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+pub async fn delete_note_handler(
+    auth_user: AuthUser,
+    Path(id): Path<String>,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let note_service = data.note_service.clone();
+
+    let deleted_note = note_service.delete_note(id, Some(&auth_user.id)).await?;
+
+    let json_response = serde_json::json!({
+        "status": "OK",
+        "message": "Note deleted successfully",
+        "data": deleted_note,
+    });
+
+    Ok(Json(json_response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notes/{id}/history",
+    tag = "Notes",
+    params(
+        ("id" = String, Path, description = "Note ID to fetch audit history for")
+    ),
+    responses(
+        (status = 200, description = "Audit history retrieved successfully", body = serde_json::Value),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Note not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+pub async fn get_note_history_handler(
+    auth_user: AuthUser,
+    Path(id): Path<String>,
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let note_service = data.note_service.clone();
+
+    let history = note_service.get_history(id, Some(&auth_user.id)).await?;
+
+    let json_response = serde_json::json!({
+        "status": "OK",
+        "message": "Audit history retrieved successfully",
+        "data": history,
+    });
+
+    Ok(Json(json_response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notes/stream",
+    tag = "Notes",
+    responses(
+        (status = 200, description = "Server-sent stream of note changes", body = serde_json::Value)
+    )
+)]
+pub async fn get_notes_stream_handler(
+    State(data): State<Arc<AppState>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let rx = data.note_changes.subscribe();
+
+    let stream = BroadcastStream::new(rx).filter_map(|event| {
+        let event = event.ok()?;
+        let data = serde_json::to_string(&event).ok()?;
+        Some(Ok(Event::default().data(data)))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+}
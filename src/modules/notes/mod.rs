@@ -1,28 +1,59 @@
-use std::sync::Arc;
-
 use serde::{Deserialize, Serialize};
 use shaku::module;
 use utoipa::ToSchema;
 
+pub mod audit;
+pub mod cdc;
 pub mod handler;
 pub mod repository;
 pub mod routes;
 pub mod service;
 
+#[cfg(feature = "sled-storage")]
+pub mod sled_repository;
+
+pub use audit::{AuditRepository, AuditRepositoryImpl, AuditRepositoryImplParameters, NoopAuditRepository};
 pub use repository::{NoteRepositoryImpl, NoteRepositoryImplParameters};
 pub use service::NoteService;
 
 module! {
     pub NotesModule {
-        components = [repository::NoteRepositoryImpl, service::NoteServiceImpl],
+        components = [repository::NoteRepositoryImpl, crate::modules::jobs::repository::JobQueueImpl],
+        providers = []
+    }
+}
+
+/// Resolved independently of `NotesModule`/`SledNotesModule` since the audit
+/// trail is always Postgres-backed regardless of which `NoteRepository`
+/// implementation is storing notes themselves.
+module! {
+    pub AuditModule {
+        components = [audit::AuditRepositoryImpl],
+        providers = []
+    }
+}
+
+#[cfg(feature = "sled-storage")]
+module! {
+    pub SledNotesModule {
+        components = [sled_repository::SledNoteRepositoryImpl],
         providers = []
     }
 }
 
 #[derive(Deserialize, Debug, Default, ToSchema)]
 pub struct FilterOptions {
-    pub page: Option<usize>,
     pub limit: Option<usize>,
+    /// Opaque cursor returned as `next_cursor` from the previous page.
+    pub after: Option<String>,
+    /// When `true`, includes soft-deleted notes in the results.
+    pub include_deleted: Option<bool>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct NotesPage {
+    pub data: Vec<crate::models::model::NoteModelResponse>,
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, ToSchema)]
@@ -39,7 +70,3 @@ pub struct UpdateNoteSchema {
     pub content: Option<String>,
     pub is_published: Option<bool>,
 }
-
-pub struct AppState {
-    pub note_service: Arc<dyn NoteService>,
-}
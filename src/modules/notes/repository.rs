@@ -1,22 +1,36 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use shaku::{Component, Interface};
 use sqlx::PgPool;
 
 use crate::models::model::NoteModel;
+use crate::modules::jobs::repository::JobQueue;
+
+/// A decoded pagination cursor: the `(created_at, sequence)` of the last row
+/// on the previous page.
+pub type NotesAfter = (DateTime<Utc>, i64);
 
 #[async_trait]
 pub trait NoteRepository: Interface + Send + Sync {
-    async fn get_all_notes(&self, limit: i32, offset: i32) -> Result<Vec<NoteModel>, sqlx::Error>;
+    /// Returns up to `limit` notes ordered by `(created_at, sequence)` DESC,
+    /// optionally starting strictly after `after`.
+    async fn get_notes_after(
+        &self,
+        limit: i32,
+        after: Option<NotesAfter>,
+        include_deleted: bool,
+    ) -> Result<Vec<NoteModel>, sqlx::Error>;
     async fn create_note(
         &self,
         id: &str,
         title: &str,
         content: &str,
         is_published: bool,
+        owner_id: Option<&str>,
     ) -> Result<NoteModel, sqlx::Error>;
-    async fn get_by_id(&self, id: &str) -> Result<NoteModel, sqlx::Error>;
+    async fn get_by_id(&self, id: &str, include_deleted: bool) -> Result<NoteModel, sqlx::Error>;
     async fn update_note(
         &self,
         id: &str,
@@ -24,24 +38,57 @@ pub trait NoteRepository: Interface + Send + Sync {
         content: &str,
         is_published: bool,
     ) -> Result<NoteModel, sqlx::Error>;
+    /// Sets `deleted_at = NOW()` instead of removing the row. Fails with
+    /// `RowNotFound` if the note doesn't exist or is already deleted.
+    async fn delete_note(&self, id: &str) -> Result<NoteModel, sqlx::Error>;
 }
 
 #[derive(Component)]
 #[shaku(interface = NoteRepository)]
 pub struct NoteRepositoryImpl {
     pool: Arc<PgPool>,
+    #[shaku(inject)]
+    job_queue: Arc<dyn JobQueue>,
 }
 
 #[async_trait]
 impl NoteRepository for NoteRepositoryImpl {
-    async fn get_all_notes(&self, limit: i32, offset: i32) -> Result<Vec<NoteModel>, sqlx::Error> {
-        sqlx::query_as::<_, NoteModel>(
-            "SELECT id, title, content, is_published, created_at, updated_at FROM notes LIMIT $1 OFFSET $2",
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&*self.pool)
-        .await
+    async fn get_notes_after(
+        &self,
+        limit: i32,
+        after: Option<NotesAfter>,
+        include_deleted: bool,
+    ) -> Result<Vec<NoteModel>, sqlx::Error> {
+        match after {
+            Some((after_created_at, after_sequence)) => {
+                sqlx::query_as::<_, NoteModel>(
+                    "SELECT id, title, content, is_published, owner_id, sequence, deleted_at, created_at, updated_at
+                     FROM notes
+                     WHERE (created_at, sequence) < ($1, $2) AND ($4 OR deleted_at IS NULL)
+                     ORDER BY created_at DESC, sequence DESC
+                     LIMIT $3",
+                )
+                .bind(after_created_at)
+                .bind(after_sequence)
+                .bind(limit)
+                .bind(include_deleted)
+                .fetch_all(&*self.pool)
+                .await
+            }
+            None => {
+                sqlx::query_as::<_, NoteModel>(
+                    "SELECT id, title, content, is_published, owner_id, sequence, deleted_at, created_at, updated_at
+                     FROM notes
+                     WHERE ($2 OR deleted_at IS NULL)
+                     ORDER BY created_at DESC, sequence DESC
+                     LIMIT $1",
+                )
+                .bind(limit)
+                .bind(include_deleted)
+                .fetch_all(&*self.pool)
+                .await
+            }
+        }
     }
 
     async fn create_note(
@@ -50,20 +97,27 @@ impl NoteRepository for NoteRepositoryImpl {
         title: &str,
         content: &str,
         is_published: bool,
+        owner_id: Option<&str>,
     ) -> Result<NoteModel, sqlx::Error> {
-        sqlx::query_as::<_, NoteModel>(
+        let mut tx = self.pool.begin().await?;
+
+        let note = sqlx::query_as::<_, NoteModel>(
             "
         INSERT INTO notes (
             id,
             title,
             content,
-            is_published
-        ) VALUES ($1, $2, $3, $4)
+            is_published,
+            owner_id
+        ) VALUES ($1, $2, $3, $4, $5)
         RETURNING
             id,
             title,
             content,
             is_published,
+            owner_id,
+            sequence,
+            deleted_at,
             created_at,
             updated_at
         ",
@@ -72,15 +126,27 @@ impl NoteRepository for NoteRepositoryImpl {
         .bind(title)
         .bind(content)
         .bind(is_published)
-        .fetch_one(&*self.pool)
-        .await
+        .bind(owner_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        self.job_queue
+            .enqueue_in_tx(&mut tx, "note.changed", serde_json::json!(note))
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(note)
     }
 
-    async fn get_by_id(&self, id: &str) -> Result<NoteModel, sqlx::Error> {
+    async fn get_by_id(&self, id: &str, include_deleted: bool) -> Result<NoteModel, sqlx::Error> {
         sqlx::query_as::<_, NoteModel>(
-            "SELECT id, title, content, is_published, created_at, updated_at FROM notes WHERE id = $1",
+            "SELECT id, title, content, is_published, owner_id, sequence, deleted_at, created_at, updated_at
+             FROM notes
+             WHERE id = $1 AND ($2 OR deleted_at IS NULL)",
         )
         .bind(id)
+        .bind(include_deleted)
         .fetch_one(&*self.pool)
         .await
     }
@@ -92,7 +158,9 @@ impl NoteRepository for NoteRepositoryImpl {
         content: &str,
         is_published: bool,
     ) -> Result<NoteModel, sqlx::Error> {
-        sqlx::query_as::<_, NoteModel>(
+        let mut tx = self.pool.begin().await?;
+
+        let note = sqlx::query_as::<_, NoteModel>(
             "
         UPDATE notes
         SET
@@ -100,12 +168,15 @@ impl NoteRepository for NoteRepositoryImpl {
             content = $3,
             is_published = $4,
             updated_at = NOW()
-        WHERE id = $1
+        WHERE id = $1 AND deleted_at IS NULL
         RETURNING
             id,
             title,
             content,
             is_published,
+            owner_id,
+            sequence,
+            deleted_at,
             created_at,
             updated_at
         ",
@@ -114,7 +185,48 @@ impl NoteRepository for NoteRepositoryImpl {
         .bind(title)
         .bind(content)
         .bind(is_published)
-        .fetch_one(&*self.pool)
-        .await
+        .fetch_one(&mut *tx)
+        .await?;
+
+        self.job_queue
+            .enqueue_in_tx(&mut tx, "note.changed", serde_json::json!(note))
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(note)
+    }
+
+    async fn delete_note(&self, id: &str) -> Result<NoteModel, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let note = sqlx::query_as::<_, NoteModel>(
+            "
+        UPDATE notes
+        SET deleted_at = NOW()
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING
+            id,
+            title,
+            content,
+            is_published,
+            owner_id,
+            sequence,
+            deleted_at,
+            created_at,
+            updated_at
+        ",
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        self.job_queue
+            .enqueue_in_tx(&mut tx, "note.changed", serde_json::json!(note))
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(note)
     }
 }
@@ -2,12 +2,14 @@ use std::sync::Arc;
 
 use axum::{
     Router,
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
 };
 
-use super::{
-    AppState,
-    handler::{create_note_handler, get_list_note_handler, update_note_handler},
+use crate::state::AppState;
+
+use super::handler::{
+    create_note_handler, delete_note_handler, get_list_note_handler, get_note_history_handler,
+    get_notes_stream_handler, update_note_handler,
 };
 
 pub fn create_notes_router(app_state: Arc<AppState>) -> Router {
@@ -15,5 +17,8 @@ pub fn create_notes_router(app_state: Arc<AppState>) -> Router {
         .route("/notes", get(get_list_note_handler))
         .route("/notes", post(create_note_handler))
         .route("/notes/{id}", put(update_note_handler))
+        .route("/notes/{id}", delete(delete_note_handler))
+        .route("/notes/{id}/history", get(get_note_history_handler))
+        .route("/notes/stream", get(get_notes_stream_handler))
         .with_state(app_state)
 }
@@ -1,83 +1,224 @@
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::models::model::{NoteModel, NoteModelResponse};
+use crate::cursor;
+use crate::error::AppError;
+use crate::models::model::{AuditModel, NoteModel, NoteModelResponse};
+use crate::modules::attachments::repository::AttachmentRepository;
 use super::{
+    audit::AuditRepository,
     repository::NoteRepository,
-    CreateNoteSchema, UpdateNoteSchema, FilterOptions,
+    CreateNoteSchema, UpdateNoteSchema, FilterOptions, NotesPage,
 };
 
+const MAX_LIMIT: i32 = 100;
+const ENTITY_NOTE: &str = "note";
+
 pub struct NoteService {
     repository: Arc<NoteRepository>,
+    audit_repository: Arc<AuditRepository>,
+    attachment_repository: Arc<AttachmentRepository>,
 }
 
 impl NoteService {
-    pub fn new(repository: Arc<NoteRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<NoteRepository>,
+        audit_repository: Arc<AuditRepository>,
+        attachment_repository: Arc<AttachmentRepository>,
+    ) -> Self {
+        Self {
+            repository,
+            audit_repository,
+            attachment_repository,
+        }
     }
 
-    pub async fn get_notes(&self, opts: FilterOptions) -> Result<Vec<NoteModelResponse>, String> {
-        let limit = opts.limit.unwrap_or(10) as i32;
-        let page = opts.page.unwrap_or(1);
-        let offset = (page - 1) * limit as usize;
+    /// Used by the attachments endpoint to 404 early on a missing (or
+    /// soft-deleted) note, and reject the upload if `actor_id` doesn't own it,
+    /// before accepting the file.
+    pub async fn ensure_note_owned(&self, id: &str, actor_id: Option<&str>) -> Result<(), AppError> {
+        let note = self.repository.get_by_id(id, false).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => AppError::NotFound("note"),
+            e => AppError::Database(e),
+        })?;
 
-        let notes = self
-            .repository
-            .get_all_notes(limit, offset as i32)
+        Self::ensure_owner(&note, actor_id)
+    }
+
+    pub async fn get_notes(&self, opts: FilterOptions) -> Result<NotesPage, AppError> {
+        let limit = (opts.limit.unwrap_or(10) as i32).clamp(1, MAX_LIMIT);
+        let include_deleted = opts.include_deleted.unwrap_or(false);
+
+        let after = opts
+            .after
+            .as_deref()
+            .and_then(cursor::decode)
+            .and_then(|(created_at_micros, sequence)| {
+                chrono::DateTime::from_timestamp_micros(created_at_micros).map(|created_at| (created_at, sequence))
+            });
+
+        let notes = self.repository.get_notes_after(limit, after, include_deleted).await?;
+
+        let next_cursor = match notes.last() {
+            Some(last) if notes.len() as i32 == limit => {
+                last.created_at.map(|created_at| cursor::encode(created_at.timestamp_micros(), last.sequence))
+            }
+            _ => None,
+        };
+
+        let note_ids: Vec<String> = notes.iter().map(|note| note.id.clone()).collect();
+        let mut attachments_by_note = self
+            .attachment_repository
+            .list_summaries_by_notes(&note_ids)
             .await
-            .map_err(|e| format!("Database error: {}", e))?;
+            .unwrap_or_default();
 
-        Ok(notes.iter().map(|note| self.to_note_response(note)).collect())
+        let data = notes
+            .iter()
+            .map(|note| {
+                let attachments = attachments_by_note.remove(&note.id).unwrap_or_default();
+                Self::to_note_response_with(note, attachments)
+            })
+            .collect();
+
+        Ok(NotesPage { data, next_cursor })
     }
 
-    pub async fn create_note(&self, note_data: CreateNoteSchema) -> Result<NoteModelResponse, String> {
+    pub async fn create_note(
+        &self,
+        note_data: CreateNoteSchema,
+        owner_id: Option<&str>,
+    ) -> Result<NoteModelResponse, AppError> {
         let id = Uuid::new_v4().to_string();
         let is_published = note_data.is_published.unwrap_or(false);
 
         let note = self
             .repository
-            .create_note(&id, &note_data.title, &note_data.content, is_published)
-            .await
-            .map_err(|e| format!("Database error: {}", e))?;
+            .create_note(&id, &note_data.title, &note_data.content, is_published, owner_id)
+            .await?;
 
-        Ok(self.to_note_response(&note))
+        self.audit_repository
+            .record(ENTITY_NOTE, &note.id, "create", owner_id, None, Some(serde_json::json!(note)))
+            .await?;
+
+        Ok(self.to_note_response(&note).await)
     }
 
     pub async fn update_note(
         &self,
         id: String,
         note_data: UpdateNoteSchema,
-    ) -> Result<NoteModelResponse, String> {
-        let _uuid = Uuid::parse_str(&id)
-            .map_err(|e| format!("Invalid UUID format: {}", e))?;
+        actor_id: Option<&str>,
+    ) -> Result<NoteModelResponse, AppError> {
+        Uuid::parse_str(&id).map_err(AppError::InvalidUuid)?;
 
-        let existing_note = self
-            .repository
-            .get_by_id(&id)
-            .await
-            .map_err(|e| format!("Database error: {}", e))?;
+        let existing_note = self.repository.get_by_id(&id, false).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => AppError::NotFound("note"),
+            e => AppError::Database(e),
+        })?;
+
+        Self::ensure_owner(&existing_note, actor_id)?;
 
-        let title = note_data.title.unwrap_or_else(|| existing_note.title);
-        let content = note_data.content.unwrap_or_else(|| existing_note.content);
+        let title = note_data.title.unwrap_or_else(|| existing_note.title.clone());
+        let content = note_data.content.unwrap_or_else(|| existing_note.content.clone());
         let is_published = note_data.is_published.unwrap_or(existing_note.is_published);
 
         let updated_note = self
             .repository
             .update_note(&id, &title, &content, is_published)
             .await
-            .map_err(|e| format!("Database error: {}", e))?;
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => AppError::NotFound("note"),
+                e => AppError::Database(e),
+            })?;
+
+        self.audit_repository
+            .record(
+                ENTITY_NOTE,
+                &id,
+                "update",
+                actor_id,
+                Some(serde_json::json!(existing_note)),
+                Some(serde_json::json!(updated_note)),
+            )
+            .await?;
+
+        Ok(self.to_note_response(&updated_note).await)
+    }
+
+    pub async fn delete_note(&self, id: String, actor_id: Option<&str>) -> Result<NoteModelResponse, AppError> {
+        Uuid::parse_str(&id).map_err(AppError::InvalidUuid)?;
+
+        let existing_note = self.repository.get_by_id(&id, false).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => AppError::NotFound("note"),
+            e => AppError::Database(e),
+        })?;
+
+        Self::ensure_owner(&existing_note, actor_id)?;
+
+        let deleted_note = self.repository.delete_note(&id).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => AppError::NotFound("note"),
+            e => AppError::Database(e),
+        })?;
+
+        self.audit_repository
+            .record(
+                ENTITY_NOTE,
+                &id,
+                "delete",
+                actor_id,
+                Some(serde_json::json!(existing_note)),
+                Some(serde_json::json!(deleted_note)),
+            )
+            .await?;
+
+        Ok(self.to_note_response(&deleted_note).await)
+    }
+
+    pub async fn get_history(&self, id: String, actor_id: Option<&str>) -> Result<Vec<AuditModel>, AppError> {
+        Uuid::parse_str(&id).map_err(AppError::InvalidUuid)?;
+
+        let note = self.repository.get_by_id(&id, false).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => AppError::NotFound("note"),
+            e => AppError::Database(e),
+        })?;
+
+        Self::ensure_owner(&note, actor_id)?;
+
+        Ok(self.audit_repository.get_history(&id).await?)
+    }
+
+    /// Rejects the request unless `actor_id` matches the note's recorded
+    /// `owner_id`. Notes with no recorded owner (created before auth existed,
+    /// or via an anonymous create) are left unrestricted since there's no
+    /// owner to check against.
+    fn ensure_owner(note: &NoteModel, actor_id: Option<&str>) -> Result<(), AppError> {
+        match &note.owner_id {
+            Some(owner) if actor_id != Some(owner.as_str()) => Err(AppError::Unauthorized),
+            _ => Ok(()),
+        }
+    }
+
+    async fn to_note_response(&self, note: &NoteModel) -> NoteModelResponse {
+        let attachments = self
+            .attachment_repository
+            .list_summaries_by_note(&note.id)
+            .await
+            .unwrap_or_default();
 
-        Ok(self.to_note_response(&updated_note))
+        Self::to_note_response_with(note, attachments)
     }
 
-    fn to_note_response(&self, note: &NoteModel) -> NoteModelResponse {
+    fn to_note_response_with(note: &NoteModel, attachments: Vec<crate::models::model::AttachmentSummary>) -> NoteModelResponse {
         NoteModelResponse {
             id: note.id.clone(),
             title: note.title.clone(),
             content: note.content.clone(),
             is_published: note.is_published,
+            owner_id: note.owner_id.clone(),
             created_at: note.created_at,
             updated_at: note.updated_at,
+            attachments,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,178 @@
+//! `NoteRepository` backed by an embedded `sled` key-value store, selected at
+//! runtime via `STORAGE_BACKEND=sled` (see `main.rs`). With this backend
+//! `main()` opens no Postgres connection and needs no `DATABASE_URL`: auth,
+//! audit and attachments fall back to no-op repositories, and neither the
+//! job worker nor the CDC stream is started.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use shaku::Component;
+
+use crate::models::model::NoteModel;
+
+use super::repository::{NoteRepository, NotesAfter};
+
+const NOTES_TREE: &str = "notes";
+const NOTES_BY_CREATED_AT_TREE: &str = "notes_by_created_at";
+
+#[derive(Component)]
+#[shaku(interface = NoteRepository)]
+pub struct SledNoteRepositoryImpl {
+    db: Arc<sled::Db>,
+}
+
+impl SledNoteRepositoryImpl {
+    /// `created_at||sequence`, zero-padded so the tree's natural byte order
+    /// matches `(created_at, sequence)` order.
+    fn index_key(note: &NoteModel) -> Vec<u8> {
+        let created_at = note.created_at.unwrap_or_else(Utc::now).timestamp_nanos_opt().unwrap_or_default();
+        format!("{:020}|{:020}", created_at, note.sequence).into_bytes()
+    }
+
+    fn notes_tree(&self) -> sled::Result<sled::Tree> {
+        self.db.open_tree(NOTES_TREE)
+    }
+
+    fn index_tree(&self) -> sled::Result<sled::Tree> {
+        self.db.open_tree(NOTES_BY_CREATED_AT_TREE)
+    }
+
+    fn sled_err(e: sled::Error) -> sqlx::Error {
+        sqlx::Error::Io(std::io::Error::other(e.to_string()))
+    }
+
+    fn decode_err(e: serde_json::Error) -> sqlx::Error {
+        sqlx::Error::Decode(Box::new(e))
+    }
+}
+
+#[async_trait]
+impl NoteRepository for SledNoteRepositoryImpl {
+    async fn get_notes_after(
+        &self,
+        limit: i32,
+        after: Option<NotesAfter>,
+        include_deleted: bool,
+    ) -> Result<Vec<NoteModel>, sqlx::Error> {
+        let notes = self.notes_tree().map_err(Self::sled_err)?;
+        let index = self.index_tree().map_err(Self::sled_err)?;
+
+        // The index is ordered ascending by `(created_at, sequence)`; the API
+        // pages newest-first, so walk it in reverse.
+        let mut iter: Box<dyn Iterator<Item = sled::Result<(sled::IVec, sled::IVec)>>> =
+            Box::new(index.iter().rev());
+
+        if let Some((after_created_at, after_sequence)) = after {
+            let cursor_key =
+                format!("{:020}|{:020}", after_created_at.timestamp_nanos_opt().unwrap_or_default(), after_sequence)
+                    .into_bytes();
+            iter = Box::new(iter.skip_while(move |entry| {
+                matches!(entry, Ok((key, _)) if key.as_ref() >= cursor_key.as_slice())
+            }));
+        }
+
+        let mut result = Vec::new();
+        for entry in iter {
+            if result.len() >= limit.max(0) as usize {
+                break;
+            }
+            let (_key, id) = entry.map_err(Self::sled_err)?;
+            if let Some(raw) = notes.get(&id).map_err(Self::sled_err)? {
+                let note: NoteModel = serde_json::from_slice(&raw).map_err(Self::decode_err)?;
+                if include_deleted || note.deleted_at.is_none() {
+                    result.push(note);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    async fn create_note(
+        &self,
+        id: &str,
+        title: &str,
+        content: &str,
+        is_published: bool,
+        owner_id: Option<&str>,
+    ) -> Result<NoteModel, sqlx::Error> {
+        let now = Utc::now();
+        let sequence = self.db.generate_id().map_err(Self::sled_err)? as i64;
+        let note = NoteModel {
+            id: id.to_string(),
+            title: title.to_string(),
+            content: content.to_string(),
+            is_published,
+            owner_id: owner_id.map(str::to_string),
+            sequence,
+            deleted_at: None,
+            created_at: Some(now),
+            updated_at: Some(now),
+        };
+
+        let notes = self.notes_tree().map_err(Self::sled_err)?;
+        let index = self.index_tree().map_err(Self::sled_err)?;
+
+        let encoded = serde_json::to_vec(&note).map_err(Self::decode_err)?;
+        notes.insert(note.id.as_bytes(), encoded).map_err(Self::sled_err)?;
+        index
+            .insert(Self::index_key(&note), note.id.as_bytes())
+            .map_err(Self::sled_err)?;
+
+        Ok(note)
+    }
+
+    async fn get_by_id(&self, id: &str, include_deleted: bool) -> Result<NoteModel, sqlx::Error> {
+        let notes = self.notes_tree().map_err(Self::sled_err)?;
+        let raw = notes.get(id.as_bytes()).map_err(Self::sled_err)?.ok_or(sqlx::Error::RowNotFound)?;
+        let note: NoteModel = serde_json::from_slice(&raw).map_err(Self::decode_err)?;
+        if !include_deleted && note.deleted_at.is_some() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(note)
+    }
+
+    async fn update_note(
+        &self,
+        id: &str,
+        title: &str,
+        content: &str,
+        is_published: bool,
+    ) -> Result<NoteModel, sqlx::Error> {
+        let existing = self.get_by_id(id, false).await?;
+
+        let updated = NoteModel {
+            id: existing.id,
+            title: title.to_string(),
+            content: content.to_string(),
+            is_published,
+            owner_id: existing.owner_id,
+            sequence: existing.sequence,
+            deleted_at: existing.deleted_at,
+            created_at: existing.created_at,
+            updated_at: Some(Utc::now()),
+        };
+
+        let notes = self.notes_tree().map_err(Self::sled_err)?;
+        let encoded = serde_json::to_vec(&updated).map_err(Self::decode_err)?;
+        notes.insert(updated.id.as_bytes(), encoded).map_err(Self::sled_err)?;
+
+        Ok(updated)
+    }
+
+    async fn delete_note(&self, id: &str) -> Result<NoteModel, sqlx::Error> {
+        let existing = self.get_by_id(id, false).await?;
+
+        let deleted = NoteModel {
+            deleted_at: Some(Utc::now()),
+            ..existing
+        };
+
+        let notes = self.notes_tree().map_err(Self::sled_err)?;
+        let encoded = serde_json::to_vec(&deleted).map_err(Self::decode_err)?;
+        notes.insert(deleted.id.as_bytes(), encoded).map_err(Self::sled_err)?;
+
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+
+use crate::modules::attachments::AttachmentService;
+use crate::modules::auth::UserService;
+use crate::modules::notes::NoteService;
+use crate::modules::notes::cdc::NoteChangeEvent;
+
+/// Shared application state handed to every router via `.with_state`.
+#[derive(Clone)]
+pub struct AppState {
+    pub note_service: Arc<NoteService>,
+    pub user_service: Arc<UserService>,
+    pub attachment_service: Arc<AttachmentService>,
+    pub note_changes: broadcast::Sender<NoteChangeEvent>,
+}